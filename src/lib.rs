@@ -1,9 +1,12 @@
-use libc::{O_RDWR, close, ioctl, open};
+use libc::{O_RDONLY, O_RDWR, close, ioctl, open};
 use std::{
     ffi::CString,
     fs::File,
     io::{Error, Result},
-    os::fd::{AsFd, RawFd},
+    os::fd::{AsFd, AsRawFd, RawFd},
+    os::unix::fs::MetadataExt,
+    thread::sleep,
+    time::Duration,
 };
 
 const LO_NAME_SIZE: usize = 64;
@@ -25,8 +28,11 @@ pub struct LoopInfo64 {
     lo_device: u64,
     lo_inode: u64,
     lo_rdevice: u64,
-    lo_offset: u64,
-    lo_sizelimit: u64,
+    /// Byte offset into the backing file where the loop device starts reading.
+    pub lo_offset: u64,
+    /// Maximum number of bytes of the backing file the loop device exposes,
+    /// or `0` for no limit.
+    pub lo_sizelimit: u64,
     lo_number: u32,
     lo_encrypt_type: u32,
     lo_encrypt_key_size: u32,
@@ -37,6 +43,40 @@ pub struct LoopInfo64 {
     lo_init: [u64; 2],
 }
 
+impl LoopInfo64 {
+    /// The loop device's assigned number (e.g. `0` for `/dev/loop0`).
+    pub fn lo_number(&self) -> u32 {
+        self.lo_number
+    }
+
+    /// The `lo_flags` bitmask, see `LO_FLAGS_*`.
+    pub fn lo_flags(&self) -> u32 {
+        self.lo_flags
+    }
+
+    /// The backing file's path as recorded by the kernel, decoded from its
+    /// NUL-terminated byte array. Truncated to `LO_NAME_SIZE` (64) bytes by
+    /// the kernel, so long paths may be cut short.
+    pub fn lo_file_name(&self) -> String {
+        decode_name(&self.lo_file_name)
+    }
+
+    /// The crypto module name, decoded from its NUL-terminated byte array.
+    /// Unused by modern loop devices (encryption was removed from the
+    /// kernel), but still part of `loop_info64`.
+    pub fn lo_crypt_name(&self) -> String {
+        decode_name(&self.lo_crypt_name)
+    }
+}
+
+/// Decodes a NUL-terminated, fixed-size byte array from `loop_info64` into a
+/// `String`, stopping at the first NUL byte (or the end of the array if
+/// there isn't one).
+fn decode_name(raw: &[u8]) -> String {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+    String::from_utf8_lossy(&raw[..end]).into_owned()
+}
+
 impl Default for LoopInfo64 {
     fn default() -> Self {
         Self {
@@ -80,6 +120,29 @@ pub const LOOP_SET_BLOCK_SIZE: u64 = 0x4C09;
 /// Configures multiple loop device parameters in a single operation
 pub const LOOP_CONFIGURE: u64 = 0x4C0A;
 
+/// Marks the loop device as read-only.
+pub const LO_FLAGS_READ_ONLY: u32 = 0x1;
+/// Automatically clears (detaches) the loop device once it is no longer
+/// opened by anyone.
+pub const LO_FLAGS_AUTOCLEAR: u32 = 0x4;
+/// Forces the kernel to scan the backing file for a partition table, so
+/// partitions appear as `/dev/loopNpM`.
+pub const LO_FLAGS_PARTSCAN: u32 = 0x8;
+
+/// The delay between retry attempts when [`Losetup::configure`] observes a
+/// transient `EAGAIN`/`EBUSY` from the kernel.
+const CONFIGURE_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The kernel's `loop_config` struct, used by the `LOOP_CONFIGURE` ioctl to
+/// associate a backing file descriptor and set status fields atomically.
+#[repr(C)]
+struct LoopConfig {
+    fd: u32,
+    block_size: u32,
+    info: LoopInfo64,
+    reserved: [u64; 8],
+}
+
 // /dev/loop-control interface
 /// Adds a new loop device to the system
 pub const LOOP_CTL_ADD: u64 = 0x4C80;
@@ -88,6 +151,133 @@ pub const LOOP_CTL_REMOVE: u64 = 0x4C81;
 /// Gets the number of the next available free loop device
 pub const LOOP_CTL_GET_FREE: u64 = 0x4C82;
 
+/// Options for [`Losetup::configure`], describing how a backing file should
+/// be associated with a loop device.
+///
+/// Build one with [`ConfigureOptions::new`] and the `with_*` methods, then
+/// pass it to [`Losetup::configure`].
+#[derive(Debug, Clone)]
+pub struct ConfigureOptions {
+    path: String,
+    offset: u64,
+    size_limit: u64,
+    block_size: u32,
+    read_only: bool,
+    retries: u32,
+}
+
+impl ConfigureOptions {
+    /// Creates options that attach `path` with no offset, no size limit, and
+    /// the kernel's default block size.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+            size_limit: 0,
+            block_size: 0,
+            read_only: false,
+            retries: 0,
+        }
+    }
+
+    /// Sets the byte offset into the backing file where the loop device
+    /// should start reading (`lo_offset`).
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Limits the loop device to `size_limit` bytes of the backing file
+    /// (`lo_sizelimit`). A value of `0` means no limit.
+    pub fn with_size_limit(mut self, size_limit: u64) -> Self {
+        self.size_limit = size_limit;
+        self
+    }
+
+    /// Sets the logical block size of the loop device (e.g. 512, 4096).
+    /// A value of `0` leaves the kernel default in place.
+    pub fn with_block_size(mut self, block_size: u32) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Opens the backing file read-only and sets `LO_FLAGS_READ_ONLY`.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets how many additional attempts [`Losetup::configure`] makes if the
+    /// kernel returns `EAGAIN`/`EBUSY` because the device isn't ready yet.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+}
+
+/// Options for [`Losetup::attach_with`], describing how a backing file
+/// should be attached and which `LoopInfo64.lo_flags` should be set.
+///
+/// Build one with [`AttachOptions::new`] and the `with_*` methods, then pass
+/// it to [`Losetup::attach_with`].
+#[derive(Debug, Clone)]
+pub struct AttachOptions {
+    path: String,
+    offset: u64,
+    size_limit: u64,
+    read_only: bool,
+    autoclear: bool,
+    part_scan: bool,
+}
+
+impl AttachOptions {
+    /// Creates options that attach `path` with no offset, no size limit, and
+    /// no flags set.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+            size_limit: 0,
+            read_only: false,
+            autoclear: false,
+            part_scan: false,
+        }
+    }
+
+    /// Sets the byte offset into the backing file (`lo_offset`).
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Limits the loop device to `size_limit` bytes of the backing file
+    /// (`lo_sizelimit`). A value of `0` means no limit.
+    pub fn with_size_limit(mut self, size_limit: u64) -> Self {
+        self.size_limit = size_limit;
+        self
+    }
+
+    /// Opens the backing file read-only and sets `LO_FLAGS_READ_ONLY`.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets `LO_FLAGS_AUTOCLEAR`, so the device detaches itself once no
+    /// longer opened by anyone.
+    pub fn with_autoclear(mut self, autoclear: bool) -> Self {
+        self.autoclear = autoclear;
+        self
+    }
+
+    /// Sets `LO_FLAGS_PARTSCAN`, so the kernel scans the backing file for a
+    /// partition table and exposes `/dev/loopNpM` devices.
+    pub fn with_part_scan(mut self, part_scan: bool) -> Self {
+        self.part_scan = part_scan;
+        self
+    }
+}
+
 /// A Simple losetup implementation for managing Linux loop devices.
 ///
 /// Loop devices allow regular files to be accessed as block devices, which is
@@ -216,6 +406,483 @@ impl Losetup {
         Ok(())
     }
 
+    /// Swaps the backing file of an attached, read-only loop device.
+    ///
+    /// Uses the `LOOP_CHANGE_FD` ioctl to change the backing store without
+    /// tearing the device down, so a mounted filesystem is not disturbed.
+    /// This supports live snapshot/overlay swaps for read-only root images.
+    ///
+    /// The kernel only permits this when the loop device is read-only and
+    /// `new_path` is the same size as the current backing file, so this
+    /// function always opens `new_path` `O_RDONLY`; it does not change the
+    /// device's read-only flag itself (see [`Losetup::attach_with`] or
+    /// [`Losetup::set_status`] to set `LO_FLAGS_READ_ONLY` beforehand).
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `new_path` - The path to the new backing file
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device or new backing file could not be opened
+    /// - The device is not read-only or `new_path` is not the same size as
+    ///   the current backing file
+    /// - The `ioctl` call fails for any other reason
+    pub fn change_fd(&self, device: &str, new_path: &str) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let file = unsafe { open(CString::new(new_path)?.as_ptr(), O_RDONLY) };
+        if file < 0 {
+            unsafe { close(loop_fd) };
+            return Err(Error::last_os_error());
+        }
+
+        let res = unsafe { ioctl(loop_fd, LOOP_CHANGE_FD, file) };
+        unsafe { close(file) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Finds a free loop device, attaches `path` to it, and returns an owned
+    /// [`LoopDevice`] handle.
+    ///
+    /// Unlike pairing [`Losetup::next_free`] with [`Losetup::attach`], the
+    /// returned handle keeps the device open and automatically clears it
+    /// with `LOOP_CLR_FD` when dropped, so an early return or panic can't
+    /// leak the device.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path to the file to be attached
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - No free loop device could be found
+    /// - The loop device or backing file could not be opened
+    /// - The `ioctl` call to attach the file fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use losetup_rs::Losetup;
+    ///
+    /// let loopctl = Losetup::open().unwrap();
+    /// let device = loopctl.attach_next("/path/to/disk.img").unwrap();
+    ///
+    /// println!("Attached to {}", device.path());
+    ///
+    /// // `device` is cleared automatically when it goes out of scope.
+    /// ```
+    pub fn attach_next(&self, path: &str) -> Result<LoopDevice> {
+        let device = self.next_free()?;
+        self.attach(&device, path)?;
+
+        let fd = unsafe { open(CString::new(device.as_str())?.as_ptr(), O_RDWR) };
+        if fd < 0 {
+            let err = Error::last_os_error();
+            let _ = self.detach(&device);
+            return Err(err);
+        }
+
+        Ok(LoopDevice { path: device, fd })
+    }
+
+    /// Creates a loop device with a specific device number.
+    ///
+    /// Uses the `LOOP_CTL_ADD` ioctl on the loop control device to
+    /// provision `/dev/loopN` for the requested `number`, rather than
+    /// letting the kernel hand back whatever [`Losetup::next_free`] finds.
+    /// This is useful on systems where `max_loop` is exhausted or when
+    /// devices need to be pre-created deterministically. Passing `-1`
+    /// asks the kernel to auto-assign the next free number instead, the
+    /// same way [`Losetup::next_free`] does.
+    ///
+    /// # Parameters
+    ///
+    /// * `number` - The desired loop device number, or `-1` to auto-assign
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path to the created loop device (e.g.,
+    /// `/dev/loop7`) on success. The path is built from the device number
+    /// the kernel actually assigned, which the `ioctl` returns on success.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - A loop device with that number already exists
+    /// - The `ioctl` call fails for any other reason
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use losetup_rs::Losetup;
+    ///
+    /// let loopctl = Losetup::open().unwrap();
+    /// let device = loopctl.add(7).unwrap();
+    ///
+    /// assert_eq!(device, "/dev/loop7");
+    /// ```
+    pub fn add(&self, number: i32) -> Result<String> {
+        let res = unsafe { ioctl(self.fd, LOOP_CTL_ADD, number) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(format!("/dev/loop{}", res))
+    }
+
+    /// Removes a loop device.
+    ///
+    /// Uses the `LOOP_CTL_REMOVE` ioctl on the loop control device to
+    /// remove `/dev/loopN` from the system.
+    ///
+    /// # Parameters
+    ///
+    /// * `number` - The loop device number to remove
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - No loop device with that number exists
+    /// - The device is still attached to a backing file
+    /// - The `ioctl` call fails for any other reason
+    pub fn remove(&self, number: i32) -> Result<()> {
+        let res = unsafe { ioctl(self.fd, LOOP_CTL_REMOVE, number) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Attaches a file to a loop device with read-only and flag support.
+    ///
+    /// Unlike [`Losetup::attach`], this opens the backing file `O_RDONLY`
+    /// when [`AttachOptions::with_read_only`] is set, and pushes
+    /// `lo_offset`, `lo_sizelimit`, and the read-only/autoclear/part-scan
+    /// flags via [`Losetup::set_status`], so callers can mount partitioned
+    /// disk images (`/dev/loop0p1`, etc.) or read-only images.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `options` - The backing file and flags to attach, see
+    ///   [`AttachOptions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device or backing file could not be opened
+    /// - The `LOOP_SET_FD` or `LOOP_SET_STATUS64` ioctl fails
+    ///
+    /// If `LOOP_SET_STATUS64` fails after `LOOP_SET_FD` already succeeded,
+    /// this function detaches the device before returning the error, so
+    /// callers never get back a device that's attached but missing its
+    /// offset/size-limit/flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use losetup_rs::{AttachOptions, Losetup};
+    ///
+    /// let loopctl = Losetup::open().unwrap();
+    /// let device = loopctl.next_free().unwrap();
+    ///
+    /// let options = AttachOptions::new("/path/to/disk.img")
+    ///     .with_read_only(true)
+    ///     .with_part_scan(true);
+    ///
+    /// loopctl.attach_with(&device, &options).unwrap();
+    /// ```
+    pub fn attach_with(&self, device: &str, options: &AttachOptions) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let file = if options.read_only {
+            unsafe { open(CString::new(options.path.as_str())?.as_ptr(), O_RDONLY) }
+        } else {
+            unsafe { open(CString::new(options.path.as_str())?.as_ptr(), O_RDWR) }
+        };
+        if file < 0 {
+            unsafe { close(loop_fd) };
+            return Err(Error::last_os_error());
+        }
+
+        let res = unsafe { ioctl(loop_fd, LOOP_SET_FD, file) };
+        unsafe { close(file) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let mut lo_flags = 0;
+        if options.read_only {
+            lo_flags |= LO_FLAGS_READ_ONLY;
+        }
+        if options.autoclear {
+            lo_flags |= LO_FLAGS_AUTOCLEAR;
+        }
+        if options.part_scan {
+            lo_flags |= LO_FLAGS_PARTSCAN;
+        }
+
+        let mut info = LoopInfo64 {
+            lo_offset: options.offset,
+            lo_sizelimit: options.size_limit,
+            lo_flags,
+            ..Default::default()
+        };
+
+        if let Err(err) = self.set_status(device, &mut info) {
+            // `LOOP_SET_FD` already succeeded, so returning now would leave
+            // the device attached with no flags/offset set. Clear it so a
+            // failed `attach_with` never leaves a half-configured device
+            // behind.
+            let _ = self.detach(device);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Sets status information for a loop device.
+    ///
+    /// This function uses the `LOOP_SET_STATUS64` ioctl command to push
+    /// offset, size limit, and flags (`LO_FLAGS_READ_ONLY`,
+    /// `LO_FLAGS_AUTOCLEAR`, `LO_FLAGS_PARTSCAN`) onto an already-attached
+    /// loop device.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `info` - The status to apply; see [`LoopInfo64`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device could not be opened
+    /// - The `ioctl` call fails (e.g., if the device is not attached)
+    pub fn set_status(&self, device: &str, info: &mut LoopInfo64) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let res = unsafe { ioctl(loop_fd, LOOP_SET_STATUS64, info) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Enables or disables direct I/O on a loop device.
+    ///
+    /// This function uses the `LOOP_SET_DIRECT_IO` ioctl command to bypass
+    /// the page cache when reading from and writing to the backing file,
+    /// which is useful when the backing file lives on fast storage.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `enabled` - Whether direct I/O should be enabled
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device could not be opened
+    /// - The `ioctl` call fails (e.g., if the backing file's filesystem
+    ///   does not support direct I/O)
+    pub fn set_direct_io(&self, device: &str, enabled: bool) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let value: libc::c_ulong = if enabled { 1 } else { 0 };
+        let res = unsafe { ioctl(loop_fd, LOOP_SET_DIRECT_IO, value) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Forces a loop device to re-read the size of its backing file.
+    ///
+    /// This function uses the `LOOP_SET_CAPACITY` ioctl command, so that a
+    /// loop device tracks a resized sparse image without requiring a
+    /// detach/reattach cycle.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device could not be opened
+    /// - The `ioctl` call fails (e.g., if the device is not attached)
+    pub fn set_capacity(&self, device: &str) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let res = unsafe { ioctl(loop_fd, LOOP_SET_CAPACITY, 0) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Sets the logical block size of a loop device.
+    ///
+    /// This function uses the `LOOP_SET_BLOCK_SIZE` ioctl command.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `size` - The logical block size in bytes (e.g., 512, 1024, 2048,
+    ///   4096)
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device could not be opened
+    /// - The `ioctl` call fails (e.g., if `size` is not a supported block
+    ///   size)
+    pub fn set_block_size(&self, device: &str, size: u32) -> Result<()> {
+        let loop_fd = unsafe { open(CString::new(device)?.as_ptr(), O_RDWR) };
+        if loop_fd < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let res = unsafe { ioctl(loop_fd, LOOP_SET_BLOCK_SIZE, size as libc::c_ulong) };
+        unsafe { close(loop_fd) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Atomically associates a backing file with a loop device using
+    /// `LOOP_CONFIGURE`.
+    ///
+    /// Unlike [`Losetup::attach`], which only issues `LOOP_SET_FD`, this sets
+    /// the offset, size limit, block size, and read-only flag in the same
+    /// ioctl, closing the race between `LOOP_SET_FD` and a follow-up
+    /// `LOOP_SET_STATUS64`.
+    ///
+    /// # Parameters
+    ///
+    /// * `device` - The path to the loop device (e.g., `/dev/loop0`)
+    /// * `options` - The backing file and parameters to configure, see
+    ///   [`ConfigureOptions`]
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The loop device or backing file could not be opened
+    /// - The `ioctl` call fails on every attempt (see
+    ///   [`ConfigureOptions::with_retries`])
+    ///
+    /// A freshly allocated device can transiently report `EAGAIN` or `EBUSY`
+    /// before it is ready; when `options` allows retries, this function
+    /// re-opens the device and tries again after a short sleep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use losetup_rs::{ConfigureOptions, Losetup};
+    ///
+    /// let loopctl = Losetup::open().unwrap();
+    /// let device = loopctl.next_free().unwrap();
+    ///
+    /// let options = ConfigureOptions::new("/path/to/disk.img")
+    ///     .with_offset(512)
+    ///     .with_retries(3);
+    ///
+    /// loopctl.configure(&device, &options).unwrap();
+    /// ```
+    pub fn configure(&self, device: &str, options: &ConfigureOptions) -> Result<()> {
+        let device_cstr = CString::new(device)?;
+
+        let file = if options.read_only {
+            File::open(&options.path)?
+        } else {
+            std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&options.path)?
+        };
+
+        let mut info = LoopInfo64 {
+            lo_offset: options.offset,
+            lo_sizelimit: options.size_limit,
+            ..Default::default()
+        };
+        if options.read_only {
+            info.lo_flags |= LO_FLAGS_READ_ONLY;
+        }
+
+        let mut config = LoopConfig {
+            fd: file.as_raw_fd() as u32,
+            block_size: options.block_size,
+            info,
+            reserved: [0; 8],
+        };
+
+        let mut attempts_left = options.retries.saturating_add(1);
+        loop {
+            let loop_fd = unsafe { open(device_cstr.as_ptr(), O_RDWR) };
+            if loop_fd < 0 {
+                return Err(Error::last_os_error());
+            }
+
+            let res = unsafe { ioctl(loop_fd, LOOP_CONFIGURE, &mut config) };
+            let err = if res < 0 {
+                Some(Error::last_os_error())
+            } else {
+                None
+            };
+            unsafe { close(loop_fd) };
+
+            match err {
+                None => return Ok(()),
+                Some(err) => {
+                    attempts_left = attempts_left.saturating_sub(1);
+                    let retryable = matches!(
+                        err.raw_os_error(),
+                        Some(libc::EAGAIN) | Some(libc::EBUSY)
+                    );
+                    if attempts_left == 0 || !retryable {
+                        return Err(err);
+                    }
+                    sleep(CONFIGURE_RETRY_DELAY);
+                }
+            }
+        }
+    }
+
     /// Detaches a file from a loop device.
     ///
     /// This function disassociates a previously attached file from a loop device,
@@ -319,6 +986,87 @@ impl Losetup {
 
         Ok(info)
     }
+
+    /// Lists every active loop device, mirroring `losetup --list`.
+    ///
+    /// Scans `/dev/loop*` and issues `LOOP_GET_STATUS64` on each one,
+    /// skipping devices that aren't currently attached to a backing file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec` of `(device path, status)` pairs for
+    /// every attached loop device, on success.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `/dev` could not be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use losetup_rs::Losetup;
+    ///
+    /// for (device, info) in Losetup::list().unwrap() {
+    ///     println!("{} -> {}", device, info.lo_file_name());
+    /// }
+    /// ```
+    pub fn list() -> Result<Vec<(String, LoopInfo64)>> {
+        let mut devices = Vec::new();
+
+        for entry in std::fs::read_dir("/dev")? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if !name.starts_with("loop") || name[4..].parse::<u32>().is_err() {
+                continue;
+            }
+
+            let device = format!("/dev/{}", name);
+            if let Ok(info) = Self::status(&device) {
+                devices.push((device, info));
+            }
+        }
+
+        Ok(devices)
+    }
+
+    /// Finds which loop device, if any, is backed by `path`.
+    ///
+    /// Lists every active loop device (see [`Losetup::list`]) and compares
+    /// each one's `lo_file_name` against `path`, falling back to comparing
+    /// the backing file's device/inode numbers (via `stat`) when the names
+    /// don't match, since `lo_file_name` is truncated to 64 bytes and may
+    /// not be an exact match for long paths.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The backing file to search for
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `Some(device path)` if a loop device backed by
+    /// `path` was found, or `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `/dev` could not be read or if
+    /// `path` does not exist.
+    pub fn find_by_backing_file(&self, path: &str) -> Result<Option<String>> {
+        let target = std::fs::metadata(path)?;
+
+        for (device, info) in Self::list()? {
+            if info.lo_file_name() == path {
+                return Ok(Some(device));
+            }
+
+            if info.lo_device == target.dev() && info.lo_inode == target.ino() {
+                return Ok(Some(device));
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl Drop for Losetup {
@@ -326,3 +1074,72 @@ impl Drop for Losetup {
         unsafe { close(self.fd) };
     }
 }
+
+/// An owned handle to an attached loop device, returned by
+/// [`Losetup::attach_next`].
+///
+/// Keeps the loop device open for the lifetime of the handle and clears it
+/// with `LOOP_CLR_FD` when dropped, so callers don't need to manually pair
+/// `attach`/`detach` calls or risk leaking the device on an early return or
+/// panic.
+pub struct LoopDevice {
+    path: String,
+    fd: RawFd,
+}
+
+impl LoopDevice {
+    /// The path to the underlying loop device (e.g., `/dev/loop0`).
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Retrieves the current status of the device.
+    ///
+    /// See [`Losetup::status`].
+    pub fn status(&self) -> Result<LoopInfo64> {
+        Losetup::status(&self.path)
+    }
+
+    /// Explicitly detaches the device, consuming the handle.
+    ///
+    /// Equivalent to letting the handle drop, but surfaces the `ioctl`
+    /// error instead of discarding it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The `ioctl` call to detach the file fails
+    /// - The device is still in use (e.g., mounted)
+    pub fn detach(mut self) -> Result<()> {
+        let res = unsafe { ioctl(self.fd, LOOP_CLR_FD) };
+        let err = if res < 0 {
+            Some(Error::last_os_error())
+        } else {
+            None
+        };
+
+        unsafe { close(self.fd) };
+        // Mark as already cleaned up so `Drop` doesn't double-clear/close
+        // `fd`, without skipping `path`'s drop glue the way `mem::forget`
+        // would (that leaked the `String`'s allocation on every call).
+        self.fd = -1;
+
+        match err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for LoopDevice {
+    fn drop(&mut self) {
+        if self.fd < 0 {
+            return;
+        }
+
+        unsafe {
+            ioctl(self.fd, LOOP_CLR_FD);
+            close(self.fd);
+        }
+    }
+}